@@ -1,3 +1,5 @@
+use base64::{engine::general_purpose, Engine as _};
+use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -162,30 +164,179 @@ pub fn rename_file(old_path: String, new_path: String) -> Result<(), String> {
         .map_err(|e| format!("Failed to rename: {}", e))
 }
 
-/// Get all markdown files in a directory recursively
-pub fn get_all_markdown_files(dir_path: &str) -> Vec<String> {
+/// Default extensions searched when the caller doesn't specify any.
+pub const DEFAULT_SEARCH_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+
+/// Fill in [`DEFAULT_SEARCH_EXTENSIONS`] when the caller didn't pass any.
+pub fn resolve_extensions(extensions: Option<Vec<String>>) -> Vec<String> {
+    extensions.unwrap_or_else(|| {
+        DEFAULT_SEARCH_EXTENSIONS
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect()
+    })
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .map(|ext| {
+            extensions
+                .iter()
+                .any(|wanted| ext.to_string_lossy().eq_ignore_ascii_case(wanted))
+        })
+        .unwrap_or(false)
+}
+
+/// Get all files matching `extensions` across multiple root directories,
+/// recursively. Used by the search commands so a query can span a note
+/// vault plus any other folders (and file types) the caller names.
+///
+/// By default this respects `.gitignore`/`.ignore`/global git excludes (via
+/// the `ignore` crate) so searches don't waste time descending into
+/// `node_modules`, build output, etc. Pass `respect_ignore: Some(false)` to
+/// force a full scan instead, which still skips dotfiles.
+pub fn get_all_markdown_files(
+    directories: &[String],
+    extensions: &[String],
+    respect_ignore: Option<bool>,
+) -> Vec<String> {
     let mut files = Vec::new();
-    
-    for entry in WalkDir::new(dir_path)
-        .follow_links(true)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        
-        // Skip hidden files and directories
-        if path
-            .file_name()
-            .map(|n| n.to_string_lossy().starts_with('.'))
-            .unwrap_or(false)
-        {
-            continue;
+
+    if respect_ignore.unwrap_or(true) {
+        for dir_path in directories {
+            let walker = WalkBuilder::new(dir_path)
+                .hidden(true)
+                .git_ignore(true)
+                .git_global(true)
+                .git_exclude(true)
+                .ignore(true)
+                .build();
+
+            for entry in walker.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if path.is_file() && has_extension(path, extensions) {
+                    files.push(path.to_string_lossy().to_string());
+                }
+            }
         }
-        
-        if path.is_file() && path.extension().map(|e| e == "md").unwrap_or(false) {
-            files.push(path.to_string_lossy().to_string());
+    } else {
+        for dir_path in directories {
+            for entry in WalkDir::new(dir_path)
+                .follow_links(true)
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let path = entry.path();
+
+                // Skip hidden entries; the contents of hidden directories
+                // are still walked
+                if path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().starts_with('.'))
+                    .unwrap_or(false)
+                {
+                    continue;
+                }
+
+                if path.is_file() && has_extension(path, extensions) {
+                    files.push(path.to_string_lossy().to_string());
+                }
+            }
         }
     }
-    
+
     files
 }
+
+/// Image extensions returned as a base64 `data:` URL by [`load_documents`]
+/// instead of a (lossy) UTF-8 read.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif"];
+
+fn mime_type_for_extension(extension: &str) -> &'static str {
+    match extension {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        _ => "application/octet-stream",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentKind {
+    Text,
+    Image,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadedDocument {
+    pub path: String,
+    pub kind: DocumentKind,
+    pub content: String,
+}
+
+fn load_document(path: &Path) -> Option<LoadedDocument> {
+    let path_str = path.to_string_lossy().to_string();
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+
+    if let Some(extension) = extension.as_deref() {
+        if IMAGE_EXTENSIONS.contains(&extension) {
+            let bytes = fs::read(path).ok()?;
+            let encoded = general_purpose::STANDARD.encode(bytes);
+
+            return Some(LoadedDocument {
+                path: path_str,
+                kind: DocumentKind::Image,
+                content: format!("data:{};base64,{}", mime_type_for_extension(extension), encoded),
+            });
+        }
+    }
+
+    let content = fs::read_to_string(path).ok()?;
+
+    Some(LoadedDocument {
+        path: path_str,
+        kind: DocumentKind::Text,
+        content,
+    })
+}
+
+/// Load a mix of files and directories in one call: directories are walked
+/// recursively (reusing the ignore-aware walker from
+/// [`get_all_markdown_files`]), text files are read as UTF-8, and images are
+/// returned as base64 `data:` URLs so binary content never hits a lossy
+/// string read.
+#[tauri::command]
+pub fn load_documents(paths: Vec<String>) -> Result<Vec<LoadedDocument>, String> {
+    let mut extensions: Vec<String> = DEFAULT_SEARCH_EXTENSIONS
+        .iter()
+        .map(|ext| ext.to_string())
+        .collect();
+    extensions.extend(IMAGE_EXTENSIONS.iter().map(|ext| ext.to_string()));
+
+    let mut documents = Vec::new();
+
+    for path_str in paths {
+        let path = Path::new(&path_str);
+
+        if !path.exists() {
+            return Err(format!("Path does not exist: {}", path_str));
+        }
+
+        if path.is_dir() {
+            let files = get_all_markdown_files(&[path_str], &extensions, Some(true));
+            for file_path in files {
+                if let Some(document) = load_document(Path::new(&file_path)) {
+                    documents.push(document);
+                }
+            }
+        } else if let Some(document) = load_document(path) {
+            documents.push(document);
+        }
+    }
+
+    Ok(documents)
+}
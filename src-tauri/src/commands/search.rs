@@ -1,17 +1,36 @@
+use once_cell::sync::{Lazy, OnceCell};
+use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::Emitter;
 
-use super::files::get_all_markdown_files;
+use super::files::{get_all_markdown_files, resolve_extensions};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Shared rayon pool for `search_content`, built once on first use so
+/// repeated searches don't pay thread spin-up cost every call.
+static SEARCH_THREAD_POOL: OnceCell<rayon::ThreadPool> = OnceCell::new();
+
+fn search_thread_pool(thread_count: Option<usize>) -> &'static rayon::ThreadPool {
+    SEARCH_THREAD_POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count.unwrap_or_else(num_cpus::get))
+            .build()
+            .expect("failed to build search thread pool")
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     pub path: String,
     pub name: String,
     pub matches: Vec<SearchMatch>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchMatch {
     pub line_number: usize,
     pub line_content: String,
@@ -19,10 +38,112 @@ pub struct SearchMatch {
     pub match_end: usize,
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct SearchMatchEvent {
+    search_id: String,
+    result: SearchResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SearchDoneEvent {
+    search_id: String,
+    total_matches: usize,
+}
+
+/// Cancellation flags for in-flight streaming searches, keyed by `search_id`.
+static CANCEL_FLAGS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn build_pattern(query: &str, case_sensitive: bool, use_regex: bool) -> Option<Regex> {
+    if !use_regex {
+        return None;
+    }
+
+    let pattern_str = if case_sensitive {
+        query.to_string()
+    } else {
+        format!("(?i){}", query)
+    };
+
+    Regex::new(&pattern_str).ok()
+}
+
+/// Scan a single file for matches, returning `None` when nothing was found
+/// (or the file could not be read as UTF-8 text).
+fn scan_file(
+    file_path: &str,
+    query: &str,
+    case_sensitive: bool,
+    use_regex: bool,
+    pattern: Option<&Regex>,
+) -> Option<SearchResult> {
+    let content = fs::read_to_string(file_path).ok()?;
+
+    let mut matches: Vec<SearchMatch> = Vec::new();
+
+    for (line_num, line) in content.lines().enumerate() {
+        let line_matches: Vec<(usize, usize)> = if use_regex {
+            pattern
+                .map(|re| re.find_iter(line).map(|m| (m.start(), m.end())).collect())
+                .unwrap_or_default()
+        } else {
+            let search_line = if case_sensitive {
+                line.to_string()
+            } else {
+                line.to_lowercase()
+            };
+            let search_query = if case_sensitive {
+                query.to_string()
+            } else {
+                query.to_lowercase()
+            };
+
+            let mut found = vec![];
+            let mut start = 0;
+            while let Some(pos) = search_line[start..].find(&search_query) {
+                let abs_pos = start + pos;
+                found.push((abs_pos, abs_pos + search_query.len()));
+                start = abs_pos + 1;
+            }
+            found
+        };
+
+        for (match_start, match_end) in line_matches {
+            matches.push(SearchMatch {
+                line_number: line_num + 1,
+                line_content: line.to_string(),
+                match_start,
+                match_end,
+            });
+        }
+    }
+
+    if matches.is_empty() {
+        return None;
+    }
+
+    let name = file_path
+        .split('/')
+        .last()
+        .unwrap_or(file_path)
+        .to_string();
+
+    Some(SearchResult {
+        path: file_path.to_string(),
+        name,
+        matches,
+    })
+}
+
 /// Search for files by name
 #[tauri::command]
-pub fn search_files(directory: String, query: String) -> Result<Vec<String>, String> {
-    let files = get_all_markdown_files(&directory);
+pub fn search_files(
+    directories: Vec<String>,
+    query: String,
+    extensions: Option<Vec<String>>,
+    respect_ignore: Option<bool>,
+) -> Result<Vec<String>, String> {
+    let files = get_all_markdown_files(&directories, &resolve_extensions(extensions), respect_ignore);
     let query_lower = query.to_lowercase();
     
     let results: Vec<String> = files
@@ -38,97 +159,107 @@ pub fn search_files(directory: String, query: String) -> Result<Vec<String>, Str
     Ok(results)
 }
 
-/// Search for content in files
+/// Search for content in files, scanning across a pool of worker threads so
+/// a large vault doesn't bottleneck on a single core.
 #[tauri::command]
 pub fn search_content(
-    directory: String,
+    directories: Vec<String>,
     query: String,
     case_sensitive: Option<bool>,
     regex_search: Option<bool>,
+    thread_count: Option<usize>,
+    extensions: Option<Vec<String>>,
+    respect_ignore: Option<bool>,
 ) -> Result<Vec<SearchResult>, String> {
-    let files = get_all_markdown_files(&directory);
+    let files = get_all_markdown_files(&directories, &resolve_extensions(extensions), respect_ignore);
     let case_sensitive = case_sensitive.unwrap_or(false);
     let use_regex = regex_search.unwrap_or(false);
-    
-    let mut results: Vec<SearchResult> = Vec::new();
-    
-    let pattern: Option<Regex> = if use_regex {
-        let pattern_str = if case_sensitive {
-            query.clone()
-        } else {
-            format!("(?i){}", query)
-        };
-        Regex::new(&pattern_str).ok()
-    } else {
-        None
-    };
-    
-    for file_path in files {
-        let content = match fs::read_to_string(&file_path) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
-        
-        let mut matches: Vec<SearchMatch> = Vec::new();
-        
-        for (line_num, line) in content.lines().enumerate() {
-            let line_matches: Vec<(usize, usize)> = if use_regex {
-                if let Some(ref re) = pattern {
-                    re.find_iter(line)
-                        .map(|m| (m.start(), m.end()))
-                        .collect()
-                } else {
-                    vec![]
-                }
-            } else {
-                let search_line = if case_sensitive {
-                    line.to_string()
-                } else {
-                    line.to_lowercase()
-                };
-                let search_query = if case_sensitive {
-                    query.clone()
-                } else {
-                    query.to_lowercase()
-                };
-                
-                let mut found = vec![];
-                let mut start = 0;
-                while let Some(pos) = search_line[start..].find(&search_query) {
-                    let abs_pos = start + pos;
-                    found.push((abs_pos, abs_pos + search_query.len()));
-                    start = abs_pos + 1;
-                }
-                found
-            };
-            
-            for (match_start, match_end) in line_matches {
-                matches.push(SearchMatch {
-                    line_number: line_num + 1,
-                    line_content: line.to_string(),
-                    match_start,
-                    match_end,
-                });
-            }
-        }
-        
-        if !matches.is_empty() {
-            let name = file_path
-                .split('/')
-                .last()
-                .unwrap_or(&file_path)
-                .to_string();
-            
-            results.push(SearchResult {
-                path: file_path,
-                name,
-                matches,
-            });
-        }
-    }
-    
+
+    let pattern = build_pattern(&query, case_sensitive, use_regex);
+
+    let mut results: Vec<SearchResult> = search_thread_pool(thread_count).install(|| {
+        files
+            .par_iter()
+            .filter_map(|file_path| {
+                scan_file(file_path, &query, case_sensitive, use_regex, pattern.as_ref())
+            })
+            .collect()
+    });
+
     // Sort by number of matches (most matches first)
     results.sort_by(|a, b| b.matches.len().cmp(&a.matches.len()));
-    
+
     Ok(results)
 }
+
+/// Streaming variant of [`search_content`] for large vaults: instead of
+/// blocking until the whole tree has been scanned, it emits a `search-match`
+/// event per matching file as the scan progresses and a final `search-done`
+/// event with the total match count. The walk runs on a background task so
+/// the command itself returns immediately; call [`cancel_search`] with the
+/// same `search_id` to stop it early (e.g. when the user keeps typing).
+#[tauri::command]
+pub async fn search_content_stream(
+    window: tauri::Window,
+    directories: Vec<String>,
+    query: String,
+    case_sensitive: Option<bool>,
+    regex_search: Option<bool>,
+    search_id: String,
+    extensions: Option<Vec<String>>,
+    respect_ignore: Option<bool>,
+) -> Result<(), String> {
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    CANCEL_FLAGS
+        .lock()
+        .unwrap()
+        .insert(search_id.clone(), cancel_flag.clone());
+    let extensions = resolve_extensions(extensions);
+
+    tauri::async_runtime::spawn(async move {
+        let files = get_all_markdown_files(&directories, &extensions, respect_ignore);
+        let case_sensitive = case_sensitive.unwrap_or(false);
+        let use_regex = regex_search.unwrap_or(false);
+        let pattern = build_pattern(&query, case_sensitive, use_regex);
+
+        let mut total_matches = 0usize;
+
+        for file_path in files {
+            if cancel_flag.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if let Some(result) = scan_file(&file_path, &query, case_sensitive, use_regex, pattern.as_ref()) {
+                total_matches += result.matches.len();
+                let _ = window.emit(
+                    "search-match",
+                    SearchMatchEvent {
+                        search_id: search_id.clone(),
+                        result,
+                    },
+                );
+            }
+        }
+
+        let _ = window.emit(
+            "search-done",
+            SearchDoneEvent {
+                search_id: search_id.clone(),
+                total_matches,
+            },
+        );
+
+        CANCEL_FLAGS.lock().unwrap().remove(&search_id);
+    });
+
+    Ok(())
+}
+
+/// Stop a streaming search started with [`search_content_stream`] before it
+/// reaches the end of the tree, e.g. because the query changed.
+#[tauri::command]
+pub fn cancel_search(search_id: String) {
+    if let Some(flag) = CANCEL_FLAGS.lock().unwrap().get(&search_id) {
+        flag.store(true, Ordering::Relaxed);
+    }
+}
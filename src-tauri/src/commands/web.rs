@@ -1,18 +1,85 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     pub title: String,
     pub url: String,
     pub content: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebSearchResponse {
     pub results: Vec<SearchResult>,
     pub error: Option<String>,
 }
 
+/// Overrides for the backends `web_search` queries. Any field left unset
+/// falls back to the current German/general defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebSearchOptions {
+    pub language: Option<String>,
+    pub categories: Option<String>,
+    pub max_results: Option<usize>,
+    pub instances: Option<Vec<String>>,
+    pub enable_duckduckgo_fallback: Option<bool>,
+}
+
+const DEFAULT_SEARXNG_INSTANCES: &[&str] = &[
+    "https://search.sapti.me",
+    "https://searx.be",
+    "https://search.ononoki.org",
+    "https://searx.tiekoetter.com",
+    "https://paulgo.io",
+];
+
+/// How long a cached response is served without re-querying any backend.
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+struct CachedResponse {
+    response: WebSearchResponse,
+    inserted_at: Instant,
+}
+
+/// In-memory cache of recent `web_search` responses, keyed by normalized
+/// query together with the options it was run with. Persists for the
+/// lifetime of the app process.
+static SEARCH_CACHE: Lazy<Mutex<HashMap<String, CachedResponse>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn normalize_query(query: &str) -> String {
+    query.trim().to_lowercase()
+}
+
+fn cache_and_return(cache_key: &str, response: WebSearchResponse) -> WebSearchResponse {
+    SEARCH_CACHE.lock().unwrap().insert(
+        cache_key.to_string(),
+        CachedResponse {
+            response: response.clone(),
+            inserted_at: Instant::now(),
+        },
+    );
+    response
+}
+
+/// Used when every backend failed: serve the most recent cached entry for
+/// this query, however stale, rather than reporting no results at all.
+fn stale_cache_or_error(cache_key: &str, fallback_error: String) -> WebSearchResponse {
+    if let Some(cached) = SEARCH_CACHE.lock().unwrap().get(cache_key) {
+        let mut response = cached.response.clone();
+        response.error = Some(format!("(veraltete Ergebnisse) {}", fallback_error));
+        return response;
+    }
+
+    WebSearchResponse {
+        results: vec![],
+        error: Some(fallback_error),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct SearxngResponse {
     results: Option<Vec<SearxngResult>>,
@@ -46,7 +113,16 @@ struct DdgRelatedTopic {
 }
 
 #[tauri::command]
-pub async fn web_search(query: String) -> WebSearchResponse {
+pub async fn web_search(query: String, options: Option<WebSearchOptions>) -> WebSearchResponse {
+    let options = options.unwrap_or_default();
+    let cache_key = format!("{}::{:?}", normalize_query(&query), options);
+
+    if let Some(cached) = SEARCH_CACHE.lock().unwrap().get(&cache_key) {
+        if cached.inserted_at.elapsed() < CACHE_TTL {
+            return cached.response.clone();
+        }
+    }
+
     let client = reqwest::Client::builder()
         .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) TypeGodMD/1.0")
         .timeout(std::time::Duration::from_secs(10))
@@ -54,21 +130,22 @@ pub async fn web_search(query: String) -> WebSearchResponse {
         .unwrap_or_else(|_| reqwest::Client::new());
 
     let encoded_query = urlencoding::encode(&query);
-    
+    let language = options.language.as_deref().unwrap_or("de");
+    let categories = options.categories.as_deref().unwrap_or("general");
+    let max_results = options.max_results.unwrap_or(8);
+
     // List of SearXNG instances to try
-    let searxng_instances = vec![
-        "https://search.sapti.me",
-        "https://searx.be",
-        "https://search.ononoki.org",
-        "https://searx.tiekoetter.com",
-        "https://paulgo.io",
-    ];
+    let default_instances: Vec<String> = DEFAULT_SEARXNG_INSTANCES
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let searxng_instances = options.instances.as_ref().unwrap_or(&default_instances);
 
     // Try SearXNG instances
-    for instance in &searxng_instances {
+    for instance in searxng_instances {
         let url = format!(
-            "{}/search?q={}&format=json&categories=general&language=de",
-            instance, encoded_query
+            "{}/search?q={}&format=json&categories={}&language={}",
+            instance, encoded_query, categories, language
         );
 
         match client.get(&url).send().await {
@@ -79,7 +156,7 @@ pub async fn web_search(query: String) -> WebSearchResponse {
                             if !results.is_empty() {
                                 let search_results: Vec<SearchResult> = results
                                     .into_iter()
-                                    .take(8)
+                                    .take(max_results)
                                     .filter_map(|r| {
                                         Some(SearchResult {
                                             title: r.title?,
@@ -90,10 +167,13 @@ pub async fn web_search(query: String) -> WebSearchResponse {
                                     .collect();
 
                                 if !search_results.is_empty() {
-                                    return WebSearchResponse {
-                                        results: search_results,
-                                        error: None,
-                                    };
+                                    return cache_and_return(
+                                        &cache_key,
+                                        WebSearchResponse {
+                                            results: search_results,
+                                            error: None,
+                                        },
+                                    );
                                 }
                             }
                         }
@@ -104,6 +184,13 @@ pub async fn web_search(query: String) -> WebSearchResponse {
         }
     }
 
+    if !options.enable_duckduckgo_fallback.unwrap_or(true) {
+        return stale_cache_or_error(
+            &cache_key,
+            "Keine Suchergebnisse gefunden. Alle Suchserver waren nicht erreichbar.".to_string(),
+        );
+    }
+
     // Fallback: Try DuckDuckGo Instant Answers API
     let ddg_url = format!(
         "https://api.duckduckgo.com/?q={}&format=json&no_html=1&skip_disambig=1",
@@ -141,24 +228,24 @@ pub async fn web_search(query: String) -> WebSearchResponse {
                     }
 
                     if !results.is_empty() {
-                        return WebSearchResponse {
-                            results,
-                            error: None,
-                        };
+                        return cache_and_return(
+                            &cache_key,
+                            WebSearchResponse {
+                                results,
+                                error: None,
+                            },
+                        );
                     }
                 }
             }
         }
         Err(e) => {
-            return WebSearchResponse {
-                results: vec![],
-                error: Some(format!("Netzwerkfehler: {}", e)),
-            };
+            return stale_cache_or_error(&cache_key, format!("Netzwerkfehler: {}", e));
         }
     }
 
-    WebSearchResponse {
-        results: vec![],
-        error: Some("Keine Suchergebnisse gefunden. Alle Suchserver waren nicht erreichbar.".to_string()),
-    }
+    stale_cache_or_error(
+        &cache_key,
+        "Keine Suchergebnisse gefunden. Alle Suchserver waren nicht erreichbar.".to_string(),
+    )
 }
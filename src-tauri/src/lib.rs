@@ -17,8 +17,11 @@ pub fn run() {
             files::create_file,
             files::delete_file,
             files::rename_file,
+            files::load_documents,
             search::search_files,
             search::search_content,
+            search::search_content_stream,
+            search::cancel_search,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");